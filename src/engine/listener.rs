@@ -4,9 +4,10 @@ use pnet::packet::ipv4::Ipv4Packet;
 use pnet::packet::Packet;
 use std::net::Ipv4Addr;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::thread;
 
-use crate::engine::scanner::{ProbeMap, RxReciver};
+use crate::engine::scanner::{PacketCounter, ProbeMap, ResponseTtl, RxReciver};
 use crate::net::interface::DeviceInterface;
 use crate::net::{icmp_builder, tcp_builder, udp_builder};
 use crate::utility::scanner_enums::{Mode, PortStatus};
@@ -19,6 +20,8 @@ use crate::utility::scanner_enums::{Mode, PortStatus};
 pub struct PacketListener {
     device_interface: Arc<DeviceInterface>,
     probe_map: ProbeMap,
+    packets_received: PacketCounter,
+    response_ttl: ResponseTtl,
     target_ip: Ipv4Addr,
     mode: Mode
 }
@@ -31,8 +34,8 @@ impl PacketListener {
     /**
      * Constructor for packet listener struct.
      */
-    pub fn new(device_interface: Arc<DeviceInterface>, probe_map: ProbeMap, target_ip: Ipv4Addr, mode: Mode) -> Self {
-        Self { device_interface, probe_map, target_ip, mode }
+    pub fn new(device_interface: Arc<DeviceInterface>, probe_map: ProbeMap, packets_received: PacketCounter, response_ttl: ResponseTtl, target_ip: Ipv4Addr, mode: Mode) -> Self {
+        Self { device_interface, probe_map, packets_received, response_ttl, target_ip, mode }
     }
 
 
@@ -56,8 +59,8 @@ impl PacketListener {
     fn handle_packet(&self, packet: &[u8]) -> Option<()> {
         // parse Ethernet header and check if its IPv4, if so continue
         let eth_header: EthernetPacket = EthernetPacket::new(packet)?;
-        if self.mode == Mode::Tcp || eth_header.get_ethertype() != EtherTypes::Ipv4 {
-            return None; //return none if mode is tcp or Ethernet header does not have IPv4
+        if eth_header.get_ethertype() != EtherTypes::Ipv4 {
+            return None; //return none if Ethernet header does not have IPv4
         }
 
         // parse IPv4 header and check if it matches our target and interface IPs, if so continue
@@ -65,6 +68,15 @@ impl PacketListener {
         if ip_header.get_source() != self.target_ip || ip_header.get_destination() != self.device_interface.ip {
             return None; //return none if doesn't match our target and interface IPs
         }
+        self.packets_received.fetch_add(1, Ordering::Relaxed); //increment received packets counter for target
+
+        self.response_ttl.store(u16::from(ip_header.get_ttl()) + 1, Ordering::Relaxed); //store TTL plus one of latest response for estimating distance to target
+
+        // if mode is tcp we do not parse packets, port status is determined by connect scan itself
+        if self.mode == Mode::Tcp {
+            return None;
+        }
+
 
         // parse the packet based on protocol type
         let parsed_packet = match ip_header.get_next_level_protocol() {
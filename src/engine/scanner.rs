@@ -1,23 +1,36 @@
 use anyhow::{anyhow, Result};
-use pnet::datalink::{DataLinkSender, DataLinkReceiver};
+use pnet::datalink::{DataLinkSender, DataLinkReceiver, NetworkInterface};
+use pnet::ipnetwork::IpNetwork;
 use pnet::util::MacAddr;
 use std::net::Ipv4Addr;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
 use std::fmt::Write;
+use std::time::{Duration, Instant};
 use tokio::sync::{Semaphore, OwnedSemaphorePermit, mpsc};
 use tokio::task::JoinHandle;
 
 use crate::engine::{udp, tcp, syn, null, fin, xmas, ack};
 use crate::engine::listener::PacketListener;
 use crate::net::interface::DeviceInterface;
-use crate::utility::scanner_enums::{Mode, PortStatus};
+use crate::utility::cli::Args;
+use crate::utility::ipv4_range::Ipv4Range;
+use crate::utility::probe_schedule::ProbeSchedule;
+use crate::utility::scanner_enums::{Mode, PortStatus, SendFailure};
 
 // define our custom types for scanner data structures
 pub type ProbeMap = Arc<Mutex<HashMap<(u16, u16), mpsc::Sender<PortStatus>>>>;
 pub type ResultsMap = Arc<Mutex<BTreeMap<u16, PortStatus>>>;
 pub type TxSender = Arc<Mutex<Box<dyn DataLinkSender>>>;
 pub type RxReciver = Box<dyn DataLinkReceiver>;
+pub type PacketCounter = Arc<AtomicU64>;
+pub type ResponseTtl = Arc<AtomicU16>;
+pub type SendFailureCounter = Arc<AtomicU64>;
+
+// define minimum probes and scan time of total silence before warning about possible asymmetric routing
+const ASYMMETRIC_ROUTING_MIN_PROBES: usize = 20;
+const ASYMMETRIC_ROUTING_MIN_SCAN_TIME: Duration = Duration::from_secs(10);
 
 
 /**
@@ -32,7 +45,10 @@ pub struct PortScanner {
     pub end_port: u16,
     pub concurrency: usize,
     pub timeout: u64,
-    pub mode: Mode
+    pub mode: Mode,
+    pub no_summary: bool,
+    pub randomize_all: bool,
+    pub send_failure: SendFailure
 }
 
 
@@ -41,25 +57,31 @@ pub struct PortScanner {
  */
 impl PortScanner {
     /**
-     * Constructor for port scanner struct.
+     * Constructor for port scanner struct from given command line arguments.
      */
-    pub fn new(device_interface: Arc<DeviceInterface>, target_ip: Ipv4Addr, start_port: u16, end_port: u16, concurrency: usize, timeout: u64, mode: Mode) -> Self {
+    pub fn new(device_interface: Arc<DeviceInterface>, args: &Args) -> Self {
         // resolve target MAC address, if failed use broadcast MAC address
-        let target_mac = DeviceInterface::resolve_device_mac_address(&device_interface, target_ip, timeout)
+        let target_mac = DeviceInterface::resolve_device_mac_address(&device_interface, args.target, args.timeout)
             .unwrap_or(MacAddr::broadcast());
-        Self { device_interface, target_ip, target_mac, start_port, end_port, concurrency, timeout, mode }
+        Self { device_interface, target_ip: args.target, target_mac, start_port: args.start_port, end_port: args.end_port, concurrency: args.concurrency as usize,
+            timeout: args.timeout, mode: args.mode, no_summary: args.no_summary, randomize_all: args.randomize_all, send_failure: args.send_failure }
     }
 
 
     /**
      * Method for running the port scanner and creating async scan tasks for each port.
+     * Returns true if any scanned port was found open, else returns false.
      */
-    pub async fn start_scan(&self) -> Result<()> {
+    pub async fn start_scan(&self) -> Result<bool> {
         // initialize our data structures for scanner tasks
+        let scan_start_time: Instant = Instant::now(); //represents start time of scan for measuring how long target was silent
         let mut scan_tasks_vec: Vec<JoinHandle<()>> = vec![]; //represents vector of scan tasks for each port
         let scan_semaphore: Arc<Semaphore> = Arc::new(Semaphore::new(self.concurrency)); //represents semaphore for limiting number of concurrent scans
         let probe_map: ProbeMap = Arc::new(Mutex::new(HashMap::new())); //represents probe map for tracking responses for each port for SYN and Xmas scans, keys are port and values are sender channel
         let results_map: ResultsMap = Arc::new(Mutex::new(BTreeMap::new())); //represents results map for storing scan result for each port, keys are port and values are port status
+        let packets_received: PacketCounter = Arc::new(AtomicU64::new(0)); //represents counter of packets received from target by listener thread
+        let send_failures: SendFailureCounter = Arc::new(AtomicU64::new(0)); //represents counter of ports whose probe could not be sent by scan tasks
+        let response_ttl: ResponseTtl = Arc::new(AtomicU16::new(0)); //represents TTL plus one of latest response received from target by listener thread, zero means no response
 
         // create new datalink channel socket and initialize our tx sender and rx receiver handles
         let (tx, rx) = DeviceInterface::create_datalink_channel(&self.device_interface)?;
@@ -67,41 +89,76 @@ impl PortScanner {
         let rx_receiver: RxReciver = rx; //initialize rx receiver handle for listener thread
 
         // create our packet listener task for capturing incoming response packets
-        let packet_listener: PacketListener = PacketListener::new(self.device_interface.clone(), probe_map.clone(), self.target_ip, self.mode);
+        let packet_listener: PacketListener = PacketListener::new(self.device_interface.clone(), probe_map.clone(), packets_received.clone(), response_ttl.clone(), self.target_ip, self.mode);
         packet_listener.start_listener(rx_receiver); //start packet listener in its own thread for handling incoming response packets
 
-        // iterate over each port in given range and create async scan task for each port
-        for target_port in self.start_port..=self.end_port {
+        // create probe schedule for our target and port range, shuffled if requested
+        // schedule supports shuffling hosts and ports jointly, but we only pass our single target so randomize all currently shuffles its ports only
+        // schedule must stay single host until probe map and results map are keyed by host and target MAC is resolved per host,
+        // otherwise responses and results from different hosts on same ports would overwrite each other
+        let probe_schedule: ProbeSchedule = ProbeSchedule::new(Ipv4Range::new(self.target_ip, self.target_ip)?, self.start_port, self.end_port, self.randomize_all);
+
+        // iterate over each probe in schedule and create async scan task for each port
+        for (target_ip, target_port) in probe_schedule {
             // acquire semaphore permit for our scan task
             let permit = scan_semaphore.clone().acquire_owned().await?;
 
             // create aysnc scan port task for port and add it to our scan tasks vector
-            scan_tasks_vec.push(tokio::spawn(Self::scan_port_task(tx_sender.clone(), probe_map.clone(), results_map.clone(),
-                self.device_interface.ip, self.device_interface.mac, self.target_ip, self.target_mac,target_port, self.timeout, self.mode, permit)));
+            scan_tasks_vec.push(tokio::spawn(Self::scan_port_task(tx_sender.clone(), probe_map.clone(), results_map.clone(), send_failures.clone(),
+                self.device_interface.ip, self.device_interface.mac, target_ip, self.target_mac, target_port, self.timeout, self.mode, self.send_failure, permit)));
         }
 
         // wait for all scan tasks to finish
+        let probes_scheduled: usize = scan_tasks_vec.len();
         for task in scan_tasks_vec {
             let _ = task.await; //call await on each task
         }
 
+        // check if listener received no packets from target during a prolonged scan, if so warn about possible asymmetric routing
+        // tcp mode is routed by the kernel and udp mode is normally silent, so only raw tcp modes are checked
+        let probes_sent: usize = probes_scheduled.saturating_sub(send_failures.load(Ordering::Relaxed) as usize);
+        let prolonged_scan: bool = probes_sent >= ASYMMETRIC_ROUTING_MIN_PROBES || (probes_sent > 0 && scan_start_time.elapsed() >= ASYMMETRIC_ROUTING_MIN_SCAN_TIME);
+        if !matches!(self.mode, Mode::Tcp | Mode::Udp) && prolonged_scan && packets_received.load(Ordering::Relaxed) == 0 {
+            self.print_asymmetric_routing_warning()?;
+        }
+
+        // report ports that could not be probed due to send failures when summary cannot show them as unscanned
+        let send_failures: u64 = send_failures.load(Ordering::Relaxed);
+        if send_failures > 0 && (self.no_summary || self.send_failure == SendFailure::Filtered) {
+            println!("\n\x1b[33mWarning:\x1b[0m {} ports could not be probed due to send failures and were recorded as {}.", send_failures, self.send_failure.port_status());
+        }
+
+        // check if any scanned port was found open for reporting findings in exit status
+        let found_open: bool = results_map.lock()
+            .map_err(|_| anyhow!("Scan failed on target {}: Could not fetch scan results for desired target.", self.target_ip))?
+            .values()
+            .any(|status| *status == PortStatus::Open);
+
+        // if summary is suppressed we skip printing scan results, note that no per-port results are printed in this case
+        if self.no_summary {
+            return Ok(found_open);
+        }
+
+        // get TTL of latest response from target for estimating its network distance
+        let response_ttl: Option<u8> = response_ttl.load(Ordering::Relaxed).checked_sub(1).map(|response_ttl| response_ttl as u8);
+
         // try to acquire lock on results map and print the summary of scan results
         if let Ok(results_map) = results_map.lock() {
-            let _ = self.print_scan_summary(&results_map).await?; //call print scan summary method
+            let _ = self.print_scan_summary(&results_map, response_ttl).await?; //call print scan summary method
         }
         // else we failed acquiring mutex, we print error message
         else {
             return Err(anyhow!("Scan failed on target {}: Could not fetch scan results for desired target.", self.target_ip));
         }
     
-        Ok(())
+        Ok(found_open)
     }
 
 
     /**
      * Static method for performing async port scan task for given port based on selected scan mode.
      */
-    async fn scan_port_task(tx: TxSender, probe_map: ProbeMap, results_map: ResultsMap, interface_ip: Ipv4Addr, interface_mac: MacAddr, target_ip: Ipv4Addr, target_mac: MacAddr, target_port: u16, timeout: u64, mode: Mode, _permit: OwnedSemaphorePermit) {
+    async fn scan_port_task(tx: TxSender, probe_map: ProbeMap, results_map: ResultsMap, send_failures: SendFailureCounter, interface_ip: Ipv4Addr, interface_mac: MacAddr, target_ip: Ipv4Addr, target_mac: MacAddr, target_port: u16, timeout: u64, mode: Mode, send_failure: SendFailure, _permit: OwnedSemaphorePermit) {
         // perform port scan on desired port based on selected scan mode
         let status = match mode {
             Mode::Udp => udp::scan_udp(tx, probe_map, interface_ip, interface_mac, target_ip, target_mac, target_port, timeout).await,
@@ -113,8 +170,10 @@ impl PortScanner {
             Mode::Ack => ack::scan_ack(tx, probe_map, interface_ip, interface_mac, target_ip, target_mac, target_port, timeout).await
         }
         .unwrap_or_else(|e| {
+            // scan methods only fail when their probe could not be transmitted, raw modes fail before sending and connect scan fails on local socket errors
             println!("Scan failed on port {}: {}", target_port, e);
-            PortStatus::Filtered
+            send_failures.fetch_add(1, Ordering::Relaxed); //increment send failures counter for port that was never probed
+            send_failure.port_status()
         });
 
         // try to acquire lock on results map and insert port status result
@@ -128,10 +187,57 @@ impl PortScanner {
     }
 
 
+    /**
+     * Method for printing warning when no response was received and other active interfaces may be receiving the responses instead.
+     * Responses routed back through a different interface than the one we listen on are never seen by our listener.
+     */
+    fn print_asymmetric_routing_warning(&self) -> Result<()> {
+        // get all active interfaces on this host and count them including our selected interface
+        let active_interfaces: Vec<NetworkInterface> = DeviceInterface::get_active_interfaces();
+        let interface_count: usize = active_interfaces.len() + usize::from(!active_interfaces.iter().any(|interface| interface.name == self.device_interface.name));
+
+        // suggest only other interfaces whose IPv4 network contains the target, if there are none we have nothing to suggest
+        let suggested_interfaces: Vec<&NetworkInterface> = active_interfaces
+            .iter()
+            .filter(|interface| interface.name != self.device_interface.name)
+            .filter(|interface| interface.ips.iter().any(|ip| matches!(ip, IpNetwork::V4(ipv4) if ipv4.contains(self.target_ip))))
+            .collect();
+        if suggested_interfaces.is_empty() {
+            return Ok(());
+        }
+
+        // define output string and write warning with suggested interfaces
+        let mut output: String = String::new();
+        writeln!(&mut output, "\n\x1b[33mWarning:\x1b[0m No response packets were received from {} on interface {}.", self.target_ip, self.device_interface.name)?;
+        writeln!(&mut output, "This host has {} active interfaces, responses may be arriving on a different interface due to asymmetric routing.", interface_count)?;
+        for interface in suggested_interfaces {
+            writeln!(&mut output, "Try scanning with: --interface {}", interface.name)?;
+        }
+
+        print!("{}", output);
+
+        Ok(())
+    }
+
+
+    /**
+     * Function that estimates network distance to target from TTL of its response.
+     * Returns estimated hop distance and initial TTL, rounded up to common initial TTL of 64, 128 or 255.
+     */
+    fn estimate_hop_distance(response_ttl: u8) -> (u8, u8) {
+        let initial_ttl: u8 = match response_ttl {
+            0..=64 => 64,
+            65..=128 => 128,
+            _ => 255
+        };
+        (initial_ttl - response_ttl, initial_ttl)
+    }
+
+
     /**
      * Method for printing scan results summary with all scanned ports and their statuses.
      */
-    async fn print_scan_summary(&self, results_map: &BTreeMap<u16, PortStatus>) -> Result<()> {
+    async fn print_scan_summary(&self, results_map: &BTreeMap<u16, PortStatus>, response_ttl: Option<u8>) -> Result<()> {
         // define output string and counters for each port status
         let mut output: String = String::new();
         let mut open: u16 = 0;
@@ -139,6 +245,7 @@ impl PortScanner {
         let mut filtered: u16 = 0;
         let mut unfiltered: u16 = 0;
         let mut open_filtered: u16 = 0;
+        let mut unscanned: u16 = 0;
         let protocol = match self.mode {
             Mode::Udp => "udp",
             _ => "tcp"
@@ -151,6 +258,13 @@ impl PortScanner {
         writeln!(&mut output, "Scan mode   : {}", self.mode)?;
         writeln!(&mut output, "Port range  : {} - {}", self.start_port, self.end_port)?;
         writeln!(&mut output, "Concurrency : {}", self.concurrency)?;
+        match response_ttl {
+            Some(response_ttl) => {
+                let (hop_distance, initial_ttl) = Self::estimate_hop_distance(response_ttl);
+                writeln!(&mut output, "Distance    : ~{} hops (TTL {} of {})", hop_distance, response_ttl, initial_ttl)?;
+            },
+            None => writeln!(&mut output, "Distance    : unknown")?
+        }
         writeln!(&mut output, "{}\n", "=".repeat(74))?;
 
         // write table header with port results
@@ -164,7 +278,8 @@ impl PortScanner {
                 PortStatus::Closed => closed += 1,
                 PortStatus::Filtered => filtered += 1,
                 PortStatus::Unfiltered => unfiltered += 1,
-                PortStatus::OpenFiltered => open_filtered += 1
+                PortStatus::OpenFiltered => open_filtered += 1,
+                PortStatus::Unscanned => unscanned += 1
             }
 
             // write port and its status to output
@@ -172,30 +287,33 @@ impl PortScanner {
         }
         writeln!(&mut output, "{}\n", "=".repeat(72))?;
 
+        // write unscanned count only if some ports could not be probed, so counts always add up to total
+        let unscanned_output: String = if unscanned > 0 { format!("Unscanned: \x1b[90m{}\x1b[0m | ", unscanned) } else { String::new() };
+
         // write final results summary with counts for each port status
         match self.mode {
             // means UDP scan mode
             Mode::Udp => {
-                writeln!(&mut output,"Results: Open: \x1b[32m{}\x1b[0m | Closed: \x1b[31m{}\x1b[0m | Filtered: \x1b[33m{}\x1b[0m | Open/Filtered: \x1b[35m{}\x1b[0m | Total: \x1b[1m{}\x1b[0m",
-                    open, closed, filtered, open_filtered, results_map.len())?;
+                writeln!(&mut output,"Results: Open: \x1b[32m{}\x1b[0m | Closed: \x1b[31m{}\x1b[0m | Filtered: \x1b[33m{}\x1b[0m | Open/Filtered: \x1b[35m{}\x1b[0m | {}Total: \x1b[1m{}\x1b[0m",
+                    open, closed, filtered, open_filtered, unscanned_output, results_map.len())?;
             },
 
             // means TCP or SYN scan modes
             Mode::Tcp | Mode::Syn => {
-                writeln!(&mut output,"Results: Open: \x1b[32m{}\x1b[0m | Closed: \x1b[31m{}\x1b[0m | Filtered: \x1b[33m{}\x1b[0m | Total: \x1b[1m{}\x1b[0m",
-                    open, closed, filtered, results_map.len())?;
+                writeln!(&mut output,"Results: Open: \x1b[32m{}\x1b[0m | Closed: \x1b[31m{}\x1b[0m | Filtered: \x1b[33m{}\x1b[0m | {}Total: \x1b[1m{}\x1b[0m",
+                    open, closed, filtered, unscanned_output, results_map.len())?;
             },
 
             // means FIN, NULL or XMAS scan modes
             Mode::Fin | Mode::Null | Mode::Xmas => {
-                writeln!(&mut output, "Results: Closed: \x1b[31m{}\x1b[0m | Open/Filtered: \x1b[35m{}\x1b[0m | Total: \x1b[1m{}\x1b[0m",
-                    closed, open_filtered, results_map.len())?;
+                writeln!(&mut output, "Results: Closed: \x1b[31m{}\x1b[0m | Open/Filtered: \x1b[35m{}\x1b[0m | {}Total: \x1b[1m{}\x1b[0m",
+                    closed, open_filtered, unscanned_output, results_map.len())?;
             },
 
             // means ACK scan mode
             Mode::Ack => {
-                writeln!(&mut output, "Results: Filtered: \x1b[33m{}\x1b[0m | Unfiltered: \x1b[36m{}\x1b[0m | Total: \x1b[1m{}\x1b[0m",
-                    filtered, unfiltered, results_map.len())?;
+                writeln!(&mut output, "Results: Filtered: \x1b[33m{}\x1b[0m | Unfiltered: \x1b[36m{}\x1b[0m | {}Total: \x1b[1m{}\x1b[0m",
+                    filtered, unfiltered, unscanned_output, results_map.len())?;
             }
         }
 
@@ -204,4 +322,20 @@ impl PortScanner {
 
         Ok(())
     }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_hop_distance_rounds_up_to_initial_ttl() {
+        assert_eq!(PortScanner::estimate_hop_distance(64), (0, 64));
+        assert_eq!(PortScanner::estimate_hop_distance(65), (63, 128));
+        assert_eq!(PortScanner::estimate_hop_distance(128), (0, 128));
+        assert_eq!(PortScanner::estimate_hop_distance(129), (126, 255));
+        assert_eq!(PortScanner::estimate_hop_distance(255), (0, 255));
+        assert_eq!(PortScanner::estimate_hop_distance(1), (63, 64));
+    }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::io::ErrorKind::*;
+use std::io::{self, ErrorKind::*};
 use tokio::net::TcpStream;
 use tokio::time::{self, Duration};
 
@@ -9,7 +9,7 @@ use crate::utility::scanner_enums::PortStatus;
 
 /**
  * Function for performing TCP connect scan on given target port.
- * Returns port status if received a response, return error if failed performing scan.
+ * Returns port status if received a response, return error if connection attempt failed locally before reaching target.
  */
 pub async fn scan_tcp(target_ip: Ipv4Addr, target_port: u16, timeout: u64) -> Result<PortStatus> {
     // create socket address for target IP and port
@@ -22,6 +22,14 @@ pub async fn scan_tcp(target_ip: Ipv4Addr, target_port: u16, timeout: u64) -> Re
             // if error occured we check what type of error occured and return port status accordingly
             match e.kind() {
                 ConnectionRefused => Ok(PortStatus::Closed),
+                // means local socket error, so probe was never sent to target
+                AddrNotAvailable | AddrInUse | PermissionDenied | NetworkDown => {
+                    Err(anyhow!("Could not connect to target with current socket: {}.", e))
+                }
+                // means local resource exhaustion such as EMFILE, ENFILE or ENOBUFS, so probe was never sent to target
+                _ if check_resource_exhaustion(&e) => {
+                    Err(anyhow!("Could not connect to target with current socket: {}.", e))
+                }
                 TimedOut | NotConnected | HostUnreachable | NetworkUnreachable => {
                     Ok(PortStatus::Filtered)
                 }
@@ -30,4 +38,18 @@ pub async fn scan_tcp(target_ip: Ipv4Addr, target_port: u16, timeout: u64) -> Re
         },
         Err(_) => Ok(PortStatus::Filtered)
     }
+}
+
+
+/**
+ * Function that checks if given connect error was caused by local resource exhaustion.
+ * Returns true if error is EMFILE, ENFILE or ENOBUFS (or their Winsock equivalents), else returns false.
+ */
+fn check_resource_exhaustion(error: &io::Error) -> bool {
+    #[cfg(unix)]
+    const RESOURCE_ERRORS: &[i32] = &[libc::EMFILE, libc::ENFILE, libc::ENOBUFS];
+    #[cfg(windows)]
+    const RESOURCE_ERRORS: &[i32] = &[10024, 10055]; //WSAEMFILE and WSAENOBUFS
+
+    error.raw_os_error().is_some_and(|code| RESOURCE_ERRORS.contains(&code))
 }
\ No newline at end of file
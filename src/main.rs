@@ -4,6 +4,7 @@ mod utility;
 
 use anyhow::Result;
 use clap::Parser;
+use std::process::ExitCode;
 use std::sync::Arc;
 
 use crate::engine::scanner::PortScanner;
@@ -13,21 +14,23 @@ use crate::utility::cli::Args;
 
 /**
  * Represents the main function for port scanner application.
+ * Exits with status 0 if any open ports were found, 2 if none were found and 1 if scan failed.
  */
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     // parse given command line arguments
     let args = Args::parse();
 
-    // create device interface for performing scans
-    let device_interface: Arc<DeviceInterface> = Arc::new(DeviceInterface::new()?);
+    // create device interface for performing scans on desired interface
+    let device_interface: Arc<DeviceInterface> = Arc::new(DeviceInterface::new(args.interface.as_deref())?);
     device_interface.show_info()?;
 
     // create port scanner instance with given arguments
-    let scanner = PortScanner::new(device_interface, args.target, args.start_port, args.end_port, args.concurrency as usize, args.timeout, args.mode);
+    let scanner = PortScanner::new(device_interface, &args);
 
-    // start the port scanning process on given target
-    scanner.start_scan().await?;
+    // start the port scanning process on given target and check if any open ports were found
+    let found_open: bool = scanner.start_scan().await?;
 
-    Ok(())
+    // return exit status based on findings, success if any open ports were found, else no findings status
+    Ok(if found_open { ExitCode::SUCCESS } else { ExitCode::from(2) })
 }
\ No newline at end of file
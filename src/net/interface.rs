@@ -30,11 +30,14 @@ pub struct DeviceInterface {
  */
 impl DeviceInterface {
     /**
-     * Function that returns an instance of DeviceInterface struct for the default network interface.
+     * Function that returns an instance of DeviceInterface struct for given network interface name, or the default network interface if not given.
      * Returns DeviceInterface instance or error if failed.
      */
-    pub fn new() -> Result<Self> {
-        let interface: NetworkInterface = Self::get_default_interface()?;
+    pub fn new(interface_name: Option<&str>) -> Result<Self> {
+        let interface: NetworkInterface = match interface_name {
+            Some(interface_name) => Self::get_interface_by_name(interface_name)?,
+            None => Self::get_default_interface()?
+        };
         let name: String = interface.name.clone();
         let description: String = Self::get_interface_description(&interface);
         let mac: MacAddr = Self::get_interface_mac_address(&interface)?;
@@ -76,11 +79,44 @@ impl DeviceInterface {
         // iterate over all available network interfaces and get a valid ipv4 interface
         datalink::interfaces()
             .into_iter()
-            .find(|interface| { !interface.is_loopback() && interface.mac.is_some() && interface.ips.iter().any(|ip| matches!(ip, IpNetwork::V4(_))) })
+            .find(Self::check_suitable_interface)
             .ok_or_else(|| anyhow!("No suitable network interface found."))
     }
 
 
+    /**
+     * Function that returns the network interface with given name.
+     * Returns network interface or error if not found or not suitable for scanning.
+     */
+    fn get_interface_by_name(interface_name: &str) -> Result<NetworkInterface> {
+        datalink::interfaces()
+            .into_iter()
+            .find(|interface| interface.name == interface_name && Self::check_suitable_interface(interface))
+            .ok_or_else(|| anyhow!("No suitable network interface found with name {}.", interface_name))
+    }
+
+
+    /**
+     * Function that returns all active network interfaces that are suitable for scanning.
+     * Returns vector of suitable interfaces that are currently up.
+     */
+    pub fn get_active_interfaces() -> Vec<NetworkInterface> {
+        datalink::interfaces()
+            .into_iter()
+            .filter(|interface| interface.is_up() && Self::check_suitable_interface(interface))
+            .collect()
+    }
+
+
+    /**
+     * Function that checks if given interface is suitable for scanning.
+     * Returns true if interface is not loopback and has MAC and IPv4 addresses, else returns false.
+     */
+    fn check_suitable_interface(interface: &NetworkInterface) -> bool {
+        !interface.is_loopback() && interface.mac.is_some() && interface.ips.iter().any(|ip| matches!(ip, IpNetwork::V4(_)))
+    }
+
+
     /**
      * Function that returns the description of the interface.
      * Returns description of interface if present, else default description.
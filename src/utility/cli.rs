@@ -1,4 +1,4 @@
-use crate::utility::scanner_enums::Mode;
+use crate::utility::scanner_enums::{Mode, SendFailure};
 use clap::Parser;
 use std::net::Ipv4Addr;
 
@@ -14,7 +14,8 @@ use std::net::Ipv4Addr;
     long_about = "High-performance asynchronous network port scanner written in Rust.\n\
                 Supports UDP, TCP, SYN, NULL, FIN, XMAS and ACK scanning techniques.\n\
                 Built with Tokio for scalable concurrency and low-level packet crafting\n\
-                to enable fast and accurate network reconnaissance.",
+                to enable fast and accurate network reconnaissance.\n\n\
+                Exit status is 0 if any open ports were found, 2 if none were found and 1 if scan failed.",
     arg_required_else_help = true,
     next_line_help = true
 )]
@@ -41,5 +42,22 @@ pub struct Args {
 
     /// Scan mode
     #[arg(short = 'm', long, value_enum, default_value_t = Mode::Syn)]
-    pub mode: Mode
+    pub mode: Mode,
+
+    /// Network interface name to scan from, uses default interface if not given
+    #[arg(short = 'i', long)]
+    pub interface: Option<String>,
+
+    /// Suppress final scan summary table, no per-port results are printed since there is no streaming output yet.
+    /// Use exit status for findings, 0 if any open ports were found, 2 if none were found and 1 if scan failed
+    #[arg(long, default_value_t = false)]
+    pub no_summary: bool,
+
+    /// Randomize port order for the target, joint host and port shuffling requires multi-host support which is not available yet
+    #[arg(long, default_value_t = false)]
+    pub randomize_all: bool,
+
+    /// Port status recorded for ports whose probe could not be sent
+    #[arg(long, value_enum, default_value_t = SendFailure::Unscanned)]
+    pub send_failure: SendFailure
 }
\ No newline at end of file
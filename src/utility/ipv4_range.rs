@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+
+/**
+ * Represents an iterable range of IPv4 addresses with optional excluded addresses.
+ */
+#[derive(Debug, Clone)]
+pub struct Ipv4Range {
+    start: Ipv4Addr,
+    end: Ipv4Addr,
+    cursor: u64,
+    exclusions: HashSet<Ipv4Addr>
+}
+
+
+/**
+ * Implementation of IPv4 range struct with methods for creating and filtering ranges.
+ */
+impl Ipv4Range {
+    /**
+     * Constructor for IPv4 range struct from start and end addresses, both inclusive.
+     * Returns IPv4 range or error if start address is greater than end address.
+     */
+    pub fn new(start: Ipv4Addr, end: Ipv4Addr) -> Result<Self> {
+        if u32::from(start) > u32::from(end) {
+            return Err(anyhow!("Invalid address range {} - {}: Start address is greater than end address.", start, end));
+        }
+
+        Ok(Self { start, end, cursor: u64::from(u32::from(start)), exclusions: HashSet::new() })
+    }
+
+
+    /**
+     * Constructor for IPv4 range struct from given address and CIDR prefix length.
+     * Network and broadcast addresses are skipped for prefixes up to /30, while /31 and /32 include all addresses.
+     * Returns IPv4 range or error if prefix length is invalid.
+     */
+    pub fn from_cidr(ip: Ipv4Addr, prefix: u8) -> Result<Self> {
+        if prefix > 32 {
+            return Err(anyhow!("Invalid CIDR prefix /{}: Prefix must be between 0 and 32.", prefix));
+        }
+
+        // calculate network and broadcast addresses using netmask of given prefix
+        let netmask: u32 = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+        let network: u32 = u32::from(ip) & netmask;
+        let broadcast: u32 = network | !netmask;
+
+        // skip network and broadcast addresses only when subnet has usable host addresses between them
+        if prefix <= 30 {
+            Self::new(Ipv4Addr::from(network + 1), Ipv4Addr::from(broadcast - 1))
+        }
+        else {
+            Self::new(Ipv4Addr::from(network), Ipv4Addr::from(broadcast))
+        }
+    }
+
+
+    /**
+     * Method that adds given addresses to the exclusion set of the range.
+     * Returns the range with updated exclusion set.
+     */
+    #[allow(dead_code)] //no CLI option takes excluded hosts yet, so only tests use this until an exclude hosts option is added
+    pub fn with_exclusions<I: IntoIterator<Item = Ipv4Addr>>(mut self, exclusions: I) -> Self {
+        self.exclusions.extend(exclusions);
+        self
+    }
+
+
+    /**
+     * Method that returns the first address of the range.
+     */
+    pub fn start(&self) -> Ipv4Addr {
+        self.start
+    }
+
+
+    /**
+     * Method that checks if given address is inside the range and not excluded.
+     * Returns true if address is part of the range, else returns false.
+     */
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        (u32::from(self.start)..=u32::from(self.end)).contains(&u32::from(ip)) && !self.exclusions.contains(&ip)
+    }
+
+
+    /**
+     * Method that returns the total number of addresses in the range without exclusions.
+     */
+    pub fn total_len(&self) -> u64 {
+        u64::from(u32::from(self.end)) - u64::from(u32::from(self.start)) + 1
+    }
+}
+
+
+/**
+ * Implement Iterator trait for IPv4 range struct, yields each non excluded address in ascending order.
+ */
+impl Iterator for Ipv4Range {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // we use u64 cursor so iterating up to 255.255.255.255 cannot overflow
+        while self.cursor <= u64::from(u32::from(self.end)) {
+            let ip: Ipv4Addr = Ipv4Addr::from(self.cursor as u32);
+            self.cursor += 1;
+
+            // return address if its not in our exclusion set
+            if !self.exclusions.contains(&ip) {
+                return Some(ip);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining: u64 = (u64::from(u32::from(self.end)) + 1).saturating_sub(self.cursor);
+        let lower: u64 = remaining.saturating_sub(self.exclusions.len() as u64);
+
+        // saturate bounds so they stay valid on targets where usize cannot hold 2^32
+        (usize::try_from(lower).unwrap_or(usize::MAX), usize::try_from(remaining).ok())
+    }
+}
+
+
+/**
+ * Implement FromStr trait for IPv4 range struct, supports single address, CIDR and start-end notations.
+ */
+impl FromStr for Ipv4Range {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s: &str = s.trim();
+
+        // means CIDR notation, for example 192.168.1.0/24
+        if let Some((ip, prefix)) = s.split_once('/') {
+            let ip: Ipv4Addr = ip.trim().parse().map_err(|_| anyhow!("Invalid IPv4 address in CIDR: {}.", s))?;
+            let prefix: u8 = prefix.trim().parse().map_err(|_| anyhow!("Invalid CIDR prefix: {}.", s))?;
+            Self::from_cidr(ip, prefix)
+        }
+        // means start-end notation, for example 192.168.1.10-192.168.1.20
+        else if let Some((start, end)) = s.split_once('-') {
+            let start: Ipv4Addr = start.trim().parse().map_err(|_| anyhow!("Invalid start IPv4 address in range: {}.", s))?;
+            let end: Ipv4Addr = end.trim().parse().map_err(|_| anyhow!("Invalid end IPv4 address in range: {}.", s))?;
+            Self::new(start, end)
+        }
+        // else means a single IPv4 address
+        else {
+            let ip: Ipv4Addr = s.parse().map_err(|_| anyhow!("Invalid IPv4 address: {}.", s))?;
+            Self::new(ip, ip)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(range: &str) -> Vec<Ipv4Addr> {
+        range.parse::<Ipv4Range>().unwrap().collect()
+    }
+
+    #[test]
+    fn cidr_31_includes_both_addresses() {
+        assert_eq!(collect("10.0.0.1/31"), vec![Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1)]);
+    }
+
+    #[test]
+    fn cidr_32_is_single_address() {
+        assert_eq!(collect("10.0.0.7/32"), vec![Ipv4Addr::new(10, 0, 0, 7)]);
+    }
+
+    #[test]
+    fn cidr_30_skips_network_and_broadcast() {
+        assert_eq!(collect("10.0.0.2/30"), vec![Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2)]);
+    }
+
+    #[test]
+    fn cidr_0_covers_all_hosts() {
+        let range: Ipv4Range = Ipv4Range::from_cidr(Ipv4Addr::new(8, 8, 8, 8), 0).unwrap();
+        assert_eq!(range.start(), Ipv4Addr::new(0, 0, 0, 1));
+        assert_eq!(range.total_len(), (1u64 << 32) - 2);
+    }
+
+    #[test]
+    fn range_crosses_octet_boundary() {
+        assert_eq!(collect("10.0.0.254-10.0.1.1"), vec![
+            Ipv4Addr::new(10, 0, 0, 254),
+            Ipv4Addr::new(10, 0, 0, 255),
+            Ipv4Addr::new(10, 0, 1, 0),
+            Ipv4Addr::new(10, 0, 1, 1)
+        ]);
+    }
+
+    #[test]
+    fn range_ending_at_max_address_does_not_overflow() {
+        let mut range: Ipv4Range = "255.255.255.254-255.255.255.255".parse().unwrap();
+        assert_eq!(range.next(), Some(Ipv4Addr::new(255, 255, 255, 254)));
+        assert_eq!(range.next(), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next(), None);
+        assert_eq!(range.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn exclusions_inside_and_outside_range() {
+        let range: Ipv4Range = "10.0.0.1-10.0.0.4".parse::<Ipv4Range>().unwrap()
+            .with_exclusions([Ipv4Addr::new(10, 0, 0, 2), Ipv4Addr::new(192, 168, 1, 1)]);
+        assert!(!range.contains(Ipv4Addr::new(10, 0, 0, 2)));
+        assert!(!range.contains(Ipv4Addr::new(192, 168, 1, 1)));
+        assert!(range.contains(Ipv4Addr::new(10, 0, 0, 3)));
+        assert_eq!(range.collect::<Vec<_>>(), vec![Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 3), Ipv4Addr::new(10, 0, 0, 4)]);
+    }
+
+    #[test]
+    fn single_address() {
+        assert_eq!(collect("192.168.1.10"), vec![Ipv4Addr::new(192, 168, 1, 10)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("10.0.0.0/33".parse::<Ipv4Range>().is_err());
+        assert!("10.0.0.0/abc".parse::<Ipv4Range>().is_err());
+        assert!("10.0.0.5-10.0.0.1".parse::<Ipv4Range>().is_err());
+        assert!("10.0.0.256".parse::<Ipv4Range>().is_err());
+        assert!("10.0.0.1-10.0.0".parse::<Ipv4Range>().is_err());
+        assert!("host/24".parse::<Ipv4Range>().is_err());
+    }
+}
\ No newline at end of file
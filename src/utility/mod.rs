@@ -1,2 +1,4 @@
 pub mod scanner_enums;
-pub mod cli;
\ No newline at end of file
+pub mod cli;
+pub mod ipv4_range;
+pub mod probe_schedule;
\ No newline at end of file
@@ -0,0 +1,177 @@
+use std::net::Ipv4Addr;
+
+use crate::utility::ipv4_range::Ipv4Range;
+
+// define number of Feistel rounds used for shuffling probe schedule
+const FEISTEL_ROUNDS: usize = 4;
+
+
+/**
+ * Represents a lazily generated schedule of (host, port) probes for a scan.
+ * When randomized, the whole host and port space is shuffled jointly so no single host sees a burst of sequential probes.
+ * The shuffle is a keyed Feistel permutation over probe indexes, so memory usage stays constant even for huge address spaces.
+ * The tradeoff is result locality, results for each host are spread across the whole scan instead of completing host by host.
+ */
+#[derive(Debug, Clone)]
+pub struct ProbeSchedule {
+    hosts: Ipv4Range,
+    start_port: u16,
+    port_count: u64,
+    total: u64,
+    domain: u64,
+    half_bits: u32,
+    round_keys: Option<[u64; FEISTEL_ROUNDS]>,
+    index: u64
+}
+
+
+/**
+ * Implementation of probe schedule struct with methods for generating probe order.
+ */
+impl ProbeSchedule {
+    /**
+     * Constructor for probe schedule struct for given hosts and port range.
+     * If randomize is false, probes are generated host by host with ports in ascending order.
+     */
+    pub fn new(hosts: Ipv4Range, start_port: u16, end_port: u16, randomize: bool) -> Self {
+        // calculate number of ports per host and total number of probes in schedule
+        let port_count: u64 = if start_port <= end_port { u64::from(end_port - start_port) + 1 } else { 0 };
+        let total: u64 = hosts.total_len() * port_count;
+
+        // calculate permutation domain as smallest power of two with even bits that covers all probes
+        let bits: u32 = u64::BITS - (total.max(2) - 1).leading_zeros();
+        let half_bits: u32 = bits.div_ceil(2);
+        let domain: u64 = 1 << (half_bits * 2);
+
+        // generate random round keys for shuffling if randomization is requested
+        let round_keys: Option<[u64; FEISTEL_ROUNDS]> = randomize.then(rand::random);
+
+        Self { hosts, start_port, port_count, total, domain, half_bits, round_keys, index: 0 }
+    }
+
+
+    /**
+     * Function that permutes given index within the domain using keyed Feistel network.
+     * Returns permuted index, each index in domain maps to exactly one other index in domain.
+     */
+    fn permute(index: u64, round_keys: &[u64; FEISTEL_ROUNDS], half_bits: u32) -> u64 {
+        let mask: u64 = (1 << half_bits) - 1;
+        let mut left: u64 = index >> half_bits;
+        let mut right: u64 = index & mask;
+
+        // perform Feistel rounds, each round swaps halves and mixes right half into left half
+        for key in round_keys {
+            let mixed: u64 = Self::mix(right ^ key) & mask;
+            (left, right) = (right, left ^ mixed);
+        }
+
+        (left << half_bits) | right
+    }
+
+
+    /**
+     * Function that mixes bits of given value using splitmix64 finalizer.
+     * Returns mixed value used as Feistel round function.
+     */
+    fn mix(value: u64) -> u64 {
+        let mut value: u64 = value.wrapping_add(0x9e3779b97f4a7c15);
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+}
+
+
+/**
+ * Implement Iterator trait for probe schedule struct, yields each (host, port) probe exactly once.
+ */
+impl Iterator for ProbeSchedule {
+    type Item = (Ipv4Addr, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // randomized schedules walk the whole power of two domain and skip indexes outside our probes
+        let limit: u64 = if self.round_keys.is_some() { self.domain } else { self.total };
+
+        while self.index < limit {
+            let index: u64 = self.index;
+            self.index += 1;
+
+            // get probe position in schedule, permuted if randomized
+            let position: u64 = match &self.round_keys {
+                Some(round_keys) => Self::permute(index, round_keys, self.half_bits),
+                None => index
+            };
+            if position >= self.total {
+                continue;
+            }
+
+            // convert probe position to host and port, skip hosts excluded from range
+            let host: Ipv4Addr = Ipv4Addr::from(u32::from(self.hosts.start()) + (position / self.port_count) as u32);
+            if !self.hosts.contains(host) {
+                continue;
+            }
+
+            return Some((host, self.start_port + (position % self.port_count) as u16));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn expected_probes(hosts: &Ipv4Range, start_port: u16, end_port: u16) -> Vec<(Ipv4Addr, u16)> {
+        hosts.clone().flat_map(|host| (start_port..=end_port).map(move |port| (host, port))).collect()
+    }
+
+    #[test]
+    fn randomized_and_sequential_yield_each_probe_once() {
+        // include totals just above powers of four so permutation domain is mostly outside our probes
+        for (hosts, start_port, end_port) in [("10.0.0.1", 1, 1), ("10.0.0.1", 1, 5), ("10.0.0.1-10.0.0.17", 1, 1), ("10.0.0.1", 1, 65), ("10.0.0.1-10.0.0.3", 100, 200), ("10.0.0.1-10.0.1.1", 80, 84)] {
+            let hosts: Ipv4Range = hosts.parse().unwrap();
+            let expected: HashSet<(Ipv4Addr, u16)> = expected_probes(&hosts, start_port, end_port).into_iter().collect();
+
+            for randomize in [false, true] {
+                let probes: Vec<(Ipv4Addr, u16)> = ProbeSchedule::new(hosts.clone(), start_port, end_port, randomize).collect();
+                let unique: HashSet<(Ipv4Addr, u16)> = probes.iter().copied().collect();
+                assert_eq!(probes.len(), expected.len());
+                assert_eq!(unique, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn full_port_range_is_permutation() {
+        let ports: HashSet<u16> = ProbeSchedule::new("10.0.0.1".parse().unwrap(), 1, 65535, true).map(|(_, port)| port).collect();
+        assert_eq!(ports.len(), 65535);
+    }
+
+    #[test]
+    fn excluded_hosts_are_skipped() {
+        let excluded: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 2);
+        let hosts: Ipv4Range = "10.0.0.1-10.0.0.3".parse::<Ipv4Range>().unwrap().with_exclusions([excluded]);
+
+        for randomize in [false, true] {
+            let probes: Vec<(Ipv4Addr, u16)> = ProbeSchedule::new(hosts.clone(), 1, 10, randomize).collect();
+            assert_eq!(probes.len(), 20);
+            assert!(probes.iter().all(|(host, _)| *host != excluded));
+        }
+    }
+
+    #[test]
+    fn empty_port_range_yields_nothing() {
+        for randomize in [false, true] {
+            assert_eq!(ProbeSchedule::new("10.0.0.1-10.0.0.5".parse().unwrap(), 10, 9, randomize).count(), 0);
+        }
+    }
+
+    #[test]
+    fn sequential_order_is_host_by_host() {
+        let hosts: Ipv4Range = "10.0.0.255-10.0.1.1".parse().unwrap();
+        let probes: Vec<(Ipv4Addr, u16)> = ProbeSchedule::new(hosts.clone(), 20, 25, false).collect();
+        assert_eq!(probes, expected_probes(&hosts, 20, 25));
+    }
+}
\ No newline at end of file
@@ -45,7 +45,8 @@ pub enum PortStatus {
     Closed,
     Filtered,
     Unfiltered,
-    OpenFiltered
+    OpenFiltered,
+    Unscanned
 }
 
 
@@ -59,8 +60,35 @@ impl fmt::Display for PortStatus {
             PortStatus::Closed => "\x1b[31mClosed\x1b[0m",
             PortStatus::Filtered => "\x1b[33mFiltered\x1b[0m",
             PortStatus::Unfiltered => "\x1b[36mUnfiltered\x1b[0m",
-            PortStatus::OpenFiltered => "\x1b[35mOpen/Filtered\x1b[0m"
+            PortStatus::OpenFiltered => "\x1b[35mOpen/Filtered\x1b[0m",
+            PortStatus::Unscanned => "\x1b[90mUnscanned\x1b[0m"
         };
         write!(f, "{output}")
     }
+}
+
+
+/**
+ * SendFailure enum that defines which port status is recorded for ports whose probe could not be sent.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SendFailure {
+    Unscanned,
+    Filtered
+}
+
+
+/**
+ * Implementation of SendFailure enum with methods for converting to port status.
+ */
+impl SendFailure {
+    /**
+     * Method that returns the port status recorded for ports that failed sending probe.
+     */
+    pub fn port_status(&self) -> PortStatus {
+        match self {
+            SendFailure::Unscanned => PortStatus::Unscanned,
+            SendFailure::Filtered => PortStatus::Filtered
+        }
+    }
 }
\ No newline at end of file